@@ -26,6 +26,8 @@ enum_string!(pub Opcode {
     Slt => "SLT",
     Spl => "SPL",
     Nop => "NOP",
+    Ldp => "LDP",
+    Stp => "STP",
 });
 
 enum_string!(pub PseudoOpcode {
@@ -74,7 +76,10 @@ impl Modifier {
                 } else {
                     match opcode {
                         Mov | Cmp | Seq | Sne => Modifier::I,
-                        Slt => Modifier::B,
+                        // LDP/STP are '94-only and, like SLT, never act on
+                        // a whole instruction, so they default to B instead
+                        // of MOV's I.
+                        Slt | Ldp | Stp => Modifier::B,
                         Add | Sub | Mul | Div | Mod => Modifier::F,
                         _ => unreachable!(),
                     }
@@ -101,6 +106,18 @@ impl Default for AddressMode {
     }
 }
 
+enum_string!(pub Standard {
+    Icws88 => "icws-88",
+    Icws94 => "icws-94",
+    Nop94 => "nop94",
+});
+
+impl Default for Standard {
+    fn default() -> Self {
+        Self::Icws94
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
     Label(String),
@@ -123,6 +140,28 @@ impl fmt::Display for Value {
     }
 }
 
+impl Value {
+    /// Resolve this value to a literal offset, wrapped modulo `core_size`.
+    /// A `Label` is looked up in `offsets`, failing with the undefined
+    /// label's name if it isn't present; an already-`Literal` value is
+    /// simply wrapped. Used to produce the fully-expanded load-file format,
+    /// where every value must be a bare, core-relative literal.
+    pub fn resolve(
+        &self,
+        offsets: &std::collections::HashMap<String, Offset>,
+        core_size: UOffset,
+    ) -> Result<Offset, String> {
+        let value = match self {
+            Self::Literal(value) => *value,
+            Self::Label(name) => *offsets
+                .get(name)
+                .ok_or_else(|| format!("undefined label '{name}'"))?,
+        };
+
+        Ok(value.rem_euclid(core_size as Offset))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use itertools::iproduct;
@@ -143,7 +182,7 @@ mod test {
 
     #[test]
     fn modifier_b_default() {
-        let opcodes = [Mov, Cmp, Seq, Sne];
+        let opcodes = [Mov, Cmp, Seq, Sne, Ldp, Stp];
 
         for (&opcode, &a_mode) in iproduct!(opcodes.iter(), AddressMode::iter_values()) {
             if a_mode != AddressMode::Immediate {
@@ -165,13 +204,15 @@ mod test {
             }
         }
 
-        for (&a_mode, &b_mode) in iproduct!(AddressMode::iter_values(), AddressMode::iter_values())
-        {
+        let opcodes = [Slt, Ldp, Stp];
+
+        for (&opcode, &a_mode, &b_mode) in iproduct!(
+            opcodes.iter(),
+            AddressMode::iter_values(),
+            AddressMode::iter_values()
+        ) {
             if a_mode != AddressMode::Immediate {
-                assert_eq!(
-                    Modifier::default_88_to_94(Opcode::Slt, a_mode, b_mode),
-                    Modifier::B
-                )
+                assert_eq!(Modifier::default_88_to_94(opcode, a_mode, b_mode), Modifier::B)
             }
         }
 
@@ -191,7 +232,7 @@ mod test {
 
     #[test]
     fn modifier_ab_default() {
-        let opcodes = [Mov, Cmp, Seq, Sne, Add, Sub, Mul, Div, Mod, Slt];
+        let opcodes = [Mov, Cmp, Seq, Sne, Add, Sub, Mul, Div, Mod, Slt, Ldp, Stp];
 
         for (&opcode, &b_mode) in iproduct!(opcodes.iter(), AddressMode::iter_values()) {
             assert_eq!(
@@ -246,4 +287,25 @@ mod test {
 
         assert_eq!(String::from("123"), Value::Literal(123).to_string());
     }
+
+    #[test]
+    fn value_resolve() {
+        let offsets =
+            std::collections::HashMap::from([("start".to_owned(), 5), ("end".to_owned(), 23)]);
+
+        assert_eq!(Value::Literal(3).resolve(&offsets, 10), Ok(3));
+        assert_eq!(Value::Literal(-1).resolve(&offsets, 10), Ok(9));
+        assert_eq!(Value::Label("start".to_owned()).resolve(&offsets, 10), Ok(5));
+        assert_eq!(Value::Label("end".to_owned()).resolve(&offsets, 10), Ok(3));
+    }
+
+    #[test]
+    fn value_resolve_undefined_label() {
+        let offsets = std::collections::HashMap::new();
+
+        assert_eq!(
+            Value::Label("missing".to_owned()).resolve(&offsets, 10),
+            Err("undefined label 'missing'".to_owned())
+        );
+    }
 }
\ No newline at end of file
@@ -0,0 +1,458 @@
+//! Parses Redcode source text into a [`Warrior`] that can be loaded into a
+//! [`corewars_sim::Core`][sim-core].
+//!
+//! [sim-core]: https://docs.rs/corewars-sim
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use corewars_core::{AddressMode, Modifier, Offset, Opcode, PseudoOpcode, Standard, UOffset, Value};
+
+/// The default core size assumed when resolving labels to literal offsets,
+/// per the standard ICWS'94 ruleset.
+pub const DEFAULT_CORE_SIZE: UOffset = 8000;
+
+/// A single Redcode instruction as produced by the parser. `modifier` is
+/// `None` when it was omitted in the source (always true under
+/// [`Standard::Icws88`], which has no explicit modifiers at all), in which
+/// case [`Modifier::default_88_to_94`] fills it in at load/dump time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub modifier: Option<Modifier>,
+    pub a_mode: AddressMode,
+    pub a_value: Value,
+    pub b_mode: AddressMode,
+    pub b_value: Value,
+}
+
+impl Instruction {
+    /// The modifier this instruction executes with: the one given
+    /// explicitly in the source, or the '88->'94 default if it was omitted.
+    pub fn resolved_modifier(&self) -> Modifier {
+        self.modifier
+            .unwrap_or_else(|| Modifier::default_88_to_94(self.opcode, self.a_mode, self.b_mode))
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}.{} {}{}, {}{}",
+            self.opcode.to_string(),
+            self.resolved_modifier().to_string(),
+            self.a_mode.to_string(),
+            self.a_value,
+            self.b_mode.to_string(),
+            self.b_value,
+        )
+    }
+}
+
+/// A fully parsed warrior: its instructions in core-relative order, the
+/// offset execution should begin at (set via `ORG`, defaulting to 0), and
+/// the label table needed to resolve `Value::Label`s to literal offsets.
+#[derive(Clone, Debug, Default)]
+pub struct Warrior {
+    pub instructions: Vec<Instruction>,
+    pub start_offset: Offset,
+    pub label_offsets: HashMap<String, Offset>,
+}
+
+impl Warrior {
+    /// Render the canonical, fully-resolved ICWS load-file format: every
+    /// label resolved to a literal (modulo [`DEFAULT_CORE_SIZE`]), every
+    /// omitted modifier filled in via the '88->'94 default, one instruction
+    /// per line as `OPCODE.MODIFIER A_MODE A_VALUE, B_MODE B_VALUE`,
+    /// preceded by an `ORG`/start-offset comment.
+    pub fn to_expanded_load_file(&self) -> std::result::Result<String, String> {
+        let mut output = format!(";assume ORG {}\n", self.start_offset);
+
+        for instruction in &self.instructions {
+            let a_value = instruction
+                .a_value
+                .resolve(&self.label_offsets, DEFAULT_CORE_SIZE)?;
+            let b_value = instruction
+                .b_value
+                .resolve(&self.label_offsets, DEFAULT_CORE_SIZE)?;
+
+            output.push_str(&format!(
+                "{}.{} {} {}, {} {}\n",
+                instruction.opcode.to_string(),
+                instruction.resolved_modifier().to_string(),
+                instruction.a_mode.to_string(),
+                a_value,
+                instruction.b_mode.to_string(),
+                b_value,
+            ));
+        }
+
+        Ok(output)
+    }
+}
+
+impl fmt::Display for Warrior {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for instruction in &self.instructions {
+            writeln!(f, "{instruction}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A non-fatal issue noticed while parsing, e.g. an unsupported pseudo-op.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning(pub String);
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A fatal parse error: the input could not be turned into a `Warrior`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error(pub String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The result of a parse attempt, carrying along any warnings regardless of
+/// whether parsing ultimately succeeded.
+pub enum Result {
+    Ok(Warrior, Vec<Warning>),
+    Err(Error, Vec<Warning>),
+}
+
+/// Parse `input` as Redcode source, validating it against `standard`.
+///
+/// Under [`Standard::Icws88`], opcodes and address modes introduced in '94
+/// (`SEQ`/`SNE`/`NOP`/`LDP`/`STP` and the pre-decrement/post-increment
+/// indirect modes `{`/`}`/`<`/`>`) are rejected, explicit modifiers are
+/// rejected outright, and the '88->'94 modifier inference is always
+/// applied. [`Standard::Nop94`] allows the full '94 instruction set except
+/// the P-space opcodes. [`Standard::Icws94`] allows everything.
+pub fn parse(input: &str, standard: Standard) -> Result {
+    let mut warnings = Vec::new();
+    let mut instructions = Vec::new();
+    let mut label_offsets = HashMap::new();
+    let mut pending_labels: Vec<String> = Vec::new();
+    let mut org_token: Option<String> = None;
+
+    for raw_line in input.lines() {
+        let line = match raw_line.find(';') {
+            Some(idx) => &raw_line[..idx],
+            None => raw_line,
+        }
+        .trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut tokens: Vec<&str> = line.split_whitespace().collect();
+
+        while let Some(&first) = tokens.first() {
+            if is_opcode_token(first) || is_pseudo_op_token(first) {
+                break;
+            }
+
+            pending_labels.push(first.to_owned());
+            tokens.remove(0);
+        }
+
+        let Some(&keyword) = tokens.first() else {
+            return Result::Err(
+                Error(format!(
+                    "expected an opcode or pseudo-op, found only labels: `{line}`"
+                )),
+                warnings,
+            );
+        };
+
+        if let Ok(PseudoOpcode::End) = PseudoOpcode::from_str(&keyword.to_uppercase()) {
+            break;
+        }
+
+        if let Ok(PseudoOpcode::Org) = PseudoOpcode::from_str(&keyword.to_uppercase()) {
+            org_token = tokens.get(1).map(|token| (*token).to_owned());
+            pending_labels.clear();
+            continue;
+        }
+
+        if PseudoOpcode::from_str(&keyword.to_uppercase()).is_ok() {
+            warnings.push(Warning(format!(
+                "ignoring unsupported pseudo-op `{keyword}`"
+            )));
+            pending_labels.clear();
+            continue;
+        }
+
+        for label in pending_labels.drain(..) {
+            label_offsets.insert(label, instructions.len() as Offset);
+        }
+
+        match parse_instruction(&tokens, standard) {
+            Ok(instruction) => instructions.push(instruction),
+            Err(err) => return Result::Err(err, warnings),
+        }
+    }
+
+    let start_offset = match org_token {
+        Some(token) => match parse_value(&token).resolve(&label_offsets, DEFAULT_CORE_SIZE) {
+            Ok(offset) => offset,
+            Err(message) => return Result::Err(Error(message), warnings),
+        },
+        None => 0,
+    };
+
+    Result::Ok(
+        Warrior {
+            instructions,
+            start_offset,
+            label_offsets,
+        },
+        warnings,
+    )
+}
+
+fn is_opcode_token(token: &str) -> bool {
+    let opcode_name = token.split('.').next().unwrap_or(token);
+    Opcode::from_str(&opcode_name.to_uppercase()).is_ok()
+}
+
+fn is_pseudo_op_token(token: &str) -> bool {
+    PseudoOpcode::from_str(&token.to_uppercase()).is_ok()
+}
+
+fn parse_instruction(
+    tokens: &[&str],
+    standard: Standard,
+) -> std::result::Result<Instruction, Error> {
+    let mut opcode_parts = tokens[0].splitn(2, '.');
+    let opcode_name = opcode_parts.next().unwrap_or_default();
+    let explicit_modifier = opcode_parts.next();
+
+    let opcode = Opcode::from_str(&opcode_name.to_uppercase())
+        .map_err(|_| Error(format!("unknown opcode `{opcode_name}`")))?;
+
+    if standard == Standard::Icws88
+        && matches!(
+            opcode,
+            Opcode::Seq | Opcode::Sne | Opcode::Nop | Opcode::Ldp | Opcode::Stp
+        )
+    {
+        return Err(Error(format!(
+            "`{}` requires ICWS'94 or later",
+            opcode.to_string()
+        )));
+    }
+
+    if standard == Standard::Nop94 && matches!(opcode, Opcode::Ldp | Opcode::Stp) {
+        return Err(Error(format!(
+            "`{}` requires P-space support, which `nop94` disables",
+            opcode.to_string()
+        )));
+    }
+
+    let modifier = match explicit_modifier {
+        Some(_) if standard == Standard::Icws88 => {
+            return Err(Error(
+                "explicit modifiers require ICWS'94 or later".to_owned(),
+            ))
+        }
+        Some(name) => Some(
+            Modifier::from_str(&name.to_uppercase())
+                .map_err(|_| Error(format!("unknown modifier `{name}`")))?,
+        ),
+        None => None,
+    };
+
+    let rest: String = tokens[1..].join(" ");
+    let mut operands = rest.splitn(2, ',');
+    let a_token: String = operands
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let b_token: String = operands
+        .next()
+        .unwrap_or("0")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+
+    let (a_mode, a_value) = parse_operand(&a_token);
+    let (b_mode, b_value) = parse_operand(&b_token);
+
+    if standard == Standard::Icws88
+        && [a_mode, b_mode].iter().any(|mode| {
+            matches!(
+                mode,
+                AddressMode::PreDecIndirectA
+                    | AddressMode::PreDecIndirectB
+                    | AddressMode::PostIncIndirectA
+                    | AddressMode::PostIncIndirectB
+            )
+        })
+    {
+        return Err(Error(
+            "the `{`/`}`/`<`/`>` indirect addressing modes require ICWS'94 or later".to_owned(),
+        ));
+    }
+
+    Ok(Instruction {
+        opcode,
+        modifier,
+        a_mode,
+        a_value,
+        b_mode,
+        b_value,
+    })
+}
+
+fn parse_operand(token: &str) -> (AddressMode, Value) {
+    let mut chars = token.chars();
+
+    if let Some(first) = chars.next() {
+        if let Ok(mode) = AddressMode::from_str(&first.to_string()) {
+            return (mode, parse_value(chars.as_str()));
+        }
+    }
+
+    (AddressMode::default(), parse_value(token))
+}
+
+fn parse_value(token: &str) -> Value {
+    match token.trim().parse::<Offset>() {
+        Ok(value) => Value::Literal(value),
+        Err(_) => Value::Label(token.trim().to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn expect_ok(input: &str) -> Warrior {
+        expect_ok_with(input, Standard::Icws94)
+    }
+
+    fn expect_ok_with(input: &str, standard: Standard) -> Warrior {
+        match parse(input, standard) {
+            Result::Ok(warrior, _) => warrior,
+            Result::Err(err, _) => panic!("expected `{input}` to parse, got {err}"),
+        }
+    }
+
+    fn expect_err_with(input: &str, standard: Standard) -> Error {
+        match parse(input, standard) {
+            Result::Ok(..) => panic!("expected `{input}` to fail to parse"),
+            Result::Err(err, _) => err,
+        }
+    }
+
+    #[test]
+    fn parses_labels_org_and_omitted_b_operand() {
+        let warrior = expect_ok(
+            r#"
+            ; a comment line, and a blank line above
+            start   ADD #4, $0
+            loop    JMP start
+                    ORG loop
+            "#,
+        );
+
+        assert_eq!(warrior.instructions.len(), 2);
+        assert_eq!(warrior.label_offsets["start"], 0);
+        assert_eq!(warrior.label_offsets["loop"], 1);
+        assert_eq!(warrior.start_offset, 1);
+
+        let add = &warrior.instructions[0];
+        assert_eq!(add.opcode, Opcode::Add);
+        assert_eq!(add.a_mode, AddressMode::Immediate);
+        assert_eq!(add.a_value, Value::Literal(4));
+        assert_eq!(add.b_mode, AddressMode::Direct);
+        assert_eq!(add.b_value, Value::Literal(0));
+
+        let jmp = &warrior.instructions[1];
+        assert_eq!(jmp.opcode, Opcode::Jmp);
+        assert_eq!(jmp.a_value, Value::Label("start".to_owned()));
+        assert_eq!(jmp.b_mode, AddressMode::Direct);
+        assert_eq!(jmp.b_value, Value::Literal(0));
+    }
+
+    #[test]
+    fn rejects_a_line_of_only_labels() {
+        match parse("start loop\nDAT $0, $0", Standard::Icws94) {
+            Result::Ok(..) => panic!("expected a label-only line to be rejected"),
+            Result::Err(..) => {}
+        }
+    }
+
+    #[test]
+    fn expands_to_canonical_load_file_format() {
+        let warrior = expect_ok(
+            r#"
+            start   MOV 0, start
+                    ORG start
+            "#,
+        );
+
+        assert_eq!(
+            warrior.to_expanded_load_file().unwrap(),
+            ";assume ORG 0\nMOV.I $ 0, $ 0\n"
+        );
+    }
+
+    #[test]
+    fn icws88_rejects_94_opcodes() {
+        for opcode in ["SEQ", "SNE", "NOP", "LDP", "STP"] {
+            expect_err_with(&format!("{opcode} $0, $0"), Standard::Icws88);
+        }
+    }
+
+    #[test]
+    fn icws88_allows_slt() {
+        // SLT predates ICWS'94, unlike the opcodes above, so '88 mode
+        // accepts it.
+        expect_ok_with("SLT $0, $0", Standard::Icws88);
+    }
+
+    #[test]
+    fn icws88_rejects_explicit_modifiers() {
+        expect_err_with("MOV.AB $0, $0", Standard::Icws88);
+    }
+
+    #[test]
+    fn icws88_rejects_94_indirect_modes() {
+        for operand in ["{0", "}0", "<0", ">0"] {
+            expect_err_with(&format!("MOV {operand}, $0"), Standard::Icws88);
+        }
+    }
+
+    #[test]
+    fn icws88_infers_modifier_for_omitted_modifier() {
+        let warrior = expect_ok_with("MOV $0, $1", Standard::Icws88);
+
+        assert_eq!(warrior.instructions[0].resolved_modifier(), Modifier::I);
+    }
+
+    #[test]
+    fn nop94_rejects_p_space_opcodes() {
+        expect_err_with("LDP $0, $0", Standard::Nop94);
+        expect_err_with("STP $0, $0", Standard::Nop94);
+    }
+
+    #[test]
+    fn icws94_allows_everything() {
+        expect_ok_with("SEQ.AB <0, >0", Standard::Icws94);
+    }
+}
@@ -0,0 +1,738 @@
+//! Simulates battles between one or more warriors sharing a single core.
+
+use std::{collections::VecDeque, error::Error, fmt};
+
+use corewars_core::{AddressMode, Modifier, Offset, Opcode, UOffset};
+use corewars_parser::Warrior;
+use rand::Rng;
+
+/// The default core size used by `Core::default()`.
+pub const DEFAULT_CORE_SIZE: usize = 8000;
+
+/// The minimum number of cells that must separate each warrior's randomized
+/// start offset from every other warrior already loaded into the core.
+const MIN_SEPARATION: usize = 100;
+
+/// A single resolved core cell: an opcode/modifier pair and two operands,
+/// each already reduced to a bare, core-relative offset (no more labels).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Cell {
+    opcode: Opcode,
+    modifier: Modifier,
+    a_mode: AddressMode,
+    a_value: Offset,
+    b_mode: AddressMode,
+    b_value: Offset,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}.{} {}{}, {}{}",
+            self.opcode.to_string(),
+            self.modifier.to_string(),
+            self.a_mode.to_string(),
+            self.a_value,
+            self.b_mode.to_string(),
+            self.b_value,
+        )
+    }
+}
+
+/// A warrior's private P-space, per the ICWS'94 extension. Cell 0 is
+/// reserved and read-only: it holds the previous round's result, set via
+/// [`PSpace::seed`] rather than `STP`.
+#[derive(Debug, Clone)]
+pub struct PSpace {
+    cells: Vec<Offset>,
+}
+
+impl PSpace {
+    fn new(size: usize) -> Self {
+        Self {
+            cells: vec![0; size.max(1)],
+        }
+    }
+
+    /// Seed the read-only result cell (cell 0) with the previous round's
+    /// outcome. Only meaningful across multiple rounds of the same match.
+    pub fn seed(&mut self, value: Offset) {
+        self.cells[0] = value;
+    }
+
+    /// `LDP`: read the cell at `index`, modulo the P-space size.
+    pub fn load(&self, index: Offset) -> Offset {
+        self.cells[Self::wrap(index, self.cells.len())]
+    }
+
+    /// `STP`: write `value` into the cell at `index`, modulo the P-space
+    /// size. Writes to cell 0 are ignored, since it is read-only.
+    pub fn store(&mut self, index: Offset, value: Offset) {
+        let index = Self::wrap(index, self.cells.len());
+        if index != 0 {
+            self.cells[index] = value;
+        }
+    }
+
+    fn wrap(index: Offset, size: usize) -> usize {
+        index.rem_euclid(size as Offset) as usize
+    }
+}
+
+/// The outcome of a single warrior at the end of a battle: the cycle it
+/// died on, or `None` if it survived to the end (either as the lone
+/// survivor, or in a tie at `max_cycles`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    pub died_at_cycle: Option<usize>,
+}
+
+struct LoadedWarrior {
+    process_queue: VecDeque<usize>,
+    p_space: PSpace,
+}
+
+/// A shared memory core that one or more warriors battle inside.
+pub struct Core {
+    memory: Vec<Cell>,
+    warriors: Vec<LoadedWarrior>,
+    p_space_size: usize,
+    steps_taken: usize,
+}
+
+impl Default for Core {
+    fn default() -> Self {
+        Self::with_core_size(DEFAULT_CORE_SIZE)
+    }
+}
+
+impl Core {
+    pub fn with_core_size(core_size: usize) -> Self {
+        Self {
+            memory: vec![Cell::default(); core_size],
+            warriors: Vec::new(),
+            p_space_size: core_size / 16 + 1,
+            steps_taken: 0,
+        }
+    }
+
+    /// A core of the default size, but with each warrior's P-space sized to
+    /// `p_space_size` cells instead of the usual `CORESIZE / 16 + 1`.
+    pub fn with_p_space_size(p_space_size: usize) -> Self {
+        Self {
+            p_space_size,
+            ..Self::default()
+        }
+    }
+
+    pub fn steps_taken(&self) -> usize {
+        self.steps_taken
+    }
+
+    /// Load `warrior` into the core at a randomized offset that keeps at
+    /// least [`MIN_SEPARATION`] cells between it and every warrior already
+    /// loaded, give it a single process at its own entry point, and
+    /// allocate its private P-space. Returns the P-space so callers can
+    /// seed cell 0 with a previous round's result.
+    pub fn load_warrior(&mut self, warrior: &Warrior) -> Result<&mut PSpace, Box<dyn Error>> {
+        let core_size = self.memory.len();
+        let start = self.pick_start_offset(warrior.instructions.len())?;
+
+        for (offset, instruction) in warrior.instructions.iter().enumerate() {
+            let a_value = instruction
+                .a_value
+                .resolve(&warrior.label_offsets, core_size as UOffset)?;
+            let b_value = instruction
+                .b_value
+                .resolve(&warrior.label_offsets, core_size as UOffset)?;
+
+            self.memory[(start + offset) % core_size] = Cell {
+                opcode: instruction.opcode,
+                modifier: instruction.resolved_modifier(),
+                a_mode: instruction.a_mode,
+                a_value,
+                b_mode: instruction.b_mode,
+                b_value,
+            };
+        }
+
+        let entry_point = wrap_add(
+            start,
+            warrior.start_offset.rem_euclid(core_size as Offset),
+            core_size,
+        );
+
+        self.warriors.push(LoadedWarrior {
+            process_queue: VecDeque::from([entry_point]),
+            p_space: PSpace::new(self.p_space_size),
+        });
+
+        Ok(&mut self.warriors.last_mut().unwrap().p_space)
+    }
+
+    fn pick_start_offset(&self, warrior_len: usize) -> Result<usize, Box<dyn Error>> {
+        let core_size = self.memory.len();
+
+        if warrior_len > core_size {
+            return Err("warrior is larger than the core".into());
+        }
+
+        let mut rng = rand::thread_rng();
+
+        if self.warriors.is_empty() {
+            return Ok(rng.gen_range(0..core_size));
+        }
+
+        for _ in 0..1000 {
+            let candidate = rng.gen_range(0..core_size);
+
+            let collides = self.warriors.iter().any(|loaded| {
+                let &occupied = loaded.process_queue.front().unwrap_or(&0);
+                wrap_distance(candidate, occupied, core_size) < MIN_SEPARATION
+            });
+
+            if !collides {
+                return Ok(candidate);
+            }
+        }
+
+        Err("couldn't find a start offset with enough separation from other warriors".into())
+    }
+
+    /// Run the loaded warriors round-robin, one instruction per living
+    /// warrior per cycle, until a single survivor remains or `max_cycles`
+    /// elapses (a tie). A warrior dies when its process queue empties,
+    /// i.e. once its last remaining process executes a `DAT` or divides by
+    /// zero.
+    pub fn run_battle(&mut self, max_cycles: Option<usize>) -> Vec<Outcome> {
+        let core_size = self.memory.len();
+        let mut died_at_cycle: Vec<Option<usize>> = vec![None; self.warriors.len()];
+        let mut cycle = 0;
+
+        loop {
+            let alive = died_at_cycle.iter().filter(|died| died.is_none()).count();
+
+            // With more than one warrior loaded, stop as soon as a single
+            // survivor remains (or none do). With exactly one, only stop
+            // once it has actually died -- `alive <= 1` is trivially true
+            // before it ever takes a step, and would otherwise end the
+            // battle immediately without running it.
+            if alive == 0 || (self.warriors.len() > 1 && alive <= 1) {
+                break;
+            }
+
+            if max_cycles.is_some_and(|max| cycle >= max) {
+                break;
+            }
+
+            for index in 0..self.warriors.len() {
+                if died_at_cycle[index].is_some() {
+                    continue;
+                }
+
+                let Some(pc) = self.warriors[index].process_queue.pop_front() else {
+                    died_at_cycle[index] = Some(cycle);
+                    continue;
+                };
+
+                self.steps_taken += 1;
+
+                if self.step(index, pc, core_size) && self.warriors[index].process_queue.is_empty()
+                {
+                    died_at_cycle[index] = Some(cycle);
+                }
+            }
+
+            cycle += 1;
+        }
+
+        died_at_cycle
+            .into_iter()
+            .map(|died_at_cycle| Outcome { died_at_cycle })
+            .collect()
+    }
+
+    /// Execute the instruction at `pc` on behalf of warrior `warrior_index`,
+    /// pushing whichever next program counter(s) should run again onto its
+    /// process queue. Returns `true` if the process that ran this
+    /// instruction is gone for good (it was a `DAT`, or a `DIV`/`MOD` by
+    /// zero).
+    fn step(&mut self, warrior_index: usize, pc: usize, core_size: usize) -> bool {
+        let cell = self.memory[pc];
+
+        if cell.opcode == Opcode::Dat {
+            return true;
+        }
+
+        let (a_addr, a_ptr) = self.resolve_operand(pc, cell.a_mode, cell.a_value);
+        let (b_addr, b_ptr) = self.resolve_operand(pc, cell.b_mode, cell.b_value);
+
+        let mut next = vec![wrap_add(pc, 1, core_size)];
+
+        match cell.opcode {
+            Opcode::Dat => unreachable!("handled above"),
+            Opcode::Mov if cell.modifier == Modifier::I => {
+                self.memory[b_addr] = self.memory[a_addr];
+            }
+            Opcode::Mov => {
+                for (src, dst) in modifier_fields(cell.modifier) {
+                    let value = field(self.memory[a_addr], src);
+                    set_field(&mut self.memory[b_addr], dst, value);
+                }
+            }
+            Opcode::Add | Opcode::Sub | Opcode::Mul => {
+                for (src, dst) in modifier_fields(cell.modifier) {
+                    let operand = field(self.memory[a_addr], src);
+                    let current = field(self.memory[b_addr], dst);
+                    let result = match cell.opcode {
+                        Opcode::Add => current.wrapping_add(operand),
+                        Opcode::Sub => current.wrapping_sub(operand),
+                        Opcode::Mul => current.wrapping_mul(operand),
+                        _ => unreachable!(),
+                    };
+                    set_field(&mut self.memory[b_addr], dst, wrap_value(result, core_size));
+                }
+            }
+            Opcode::Div | Opcode::Mod => {
+                let pairs = modifier_fields(cell.modifier);
+
+                if pairs
+                    .iter()
+                    .any(|&(src, _)| field(self.memory[a_addr], src) == 0)
+                {
+                    return true;
+                }
+
+                for (src, dst) in pairs {
+                    let operand = field(self.memory[a_addr], src);
+                    let current = field(self.memory[b_addr], dst);
+                    let result = if cell.opcode == Opcode::Div {
+                        current / operand
+                    } else {
+                        current % operand
+                    };
+                    set_field(&mut self.memory[b_addr], dst, wrap_value(result, core_size));
+                }
+            }
+            Opcode::Jmp => next = vec![a_addr],
+            Opcode::Jmz => {
+                if self.memory[b_addr].b_value == 0 {
+                    next = vec![a_addr];
+                }
+            }
+            Opcode::Jmn => {
+                if self.memory[b_addr].b_value != 0 {
+                    next = vec![a_addr];
+                }
+            }
+            Opcode::Djn => {
+                self.memory[b_addr].b_value = self.memory[b_addr].b_value.wrapping_sub(1);
+                if self.memory[b_addr].b_value != 0 {
+                    next = vec![a_addr];
+                }
+            }
+            Opcode::Cmp | Opcode::Seq if cell.modifier == Modifier::I => {
+                if self.memory[a_addr] == self.memory[b_addr] {
+                    next = vec![wrap_add(pc, 2, core_size)];
+                }
+            }
+            Opcode::Cmp | Opcode::Seq => {
+                if fields_equal(self.memory[a_addr], self.memory[b_addr], cell.modifier) {
+                    next = vec![wrap_add(pc, 2, core_size)];
+                }
+            }
+            Opcode::Sne if cell.modifier == Modifier::I => {
+                if self.memory[a_addr] != self.memory[b_addr] {
+                    next = vec![wrap_add(pc, 2, core_size)];
+                }
+            }
+            Opcode::Sne => {
+                if !fields_equal(self.memory[a_addr], self.memory[b_addr], cell.modifier) {
+                    next = vec![wrap_add(pc, 2, core_size)];
+                }
+            }
+            Opcode::Slt => {
+                let all_less = modifier_fields(cell.modifier).iter().all(|&(src, dst)| {
+                    field(self.memory[a_addr], src) < field(self.memory[b_addr], dst)
+                });
+
+                if all_less {
+                    next = vec![wrap_add(pc, 2, core_size)];
+                }
+            }
+            Opcode::Spl => next.push(a_addr),
+            Opcode::Nop => {}
+            Opcode::Ldp => {
+                let value = self.warriors[warrior_index].p_space.load(a_ptr);
+                self.memory[b_addr].b_value = value;
+            }
+            Opcode::Stp => {
+                let value = self.memory[a_addr].b_value;
+                self.warriors[warrior_index].p_space.store(b_ptr, value);
+            }
+        }
+
+        for pc in next {
+            self.warriors[warrior_index].process_queue.push_back(pc);
+        }
+
+        false
+    }
+
+    /// Resolve an operand against `pc`, applying any addressing-mode side
+    /// effect (pre-decrement/post-increment) exactly once. Returns the core
+    /// address to use for ordinary memory access, alongside the operand's
+    /// own evaluated pointer value -- for `#`/`$` modes that's just `value`
+    /// itself, and for indirect modes it's the dereferenced cell's A/B
+    /// field. `LDP`/`STP` index P-space with the latter, since P-space
+    /// addressing is independent of where a warrior happened to land in
+    /// core.
+    fn resolve_operand(&mut self, pc: usize, mode: AddressMode, value: Offset) -> (usize, Offset) {
+        let core_size = self.memory.len();
+        let direct = wrap_add(pc, value, core_size);
+
+        match mode {
+            AddressMode::Immediate => (pc, value),
+            AddressMode::Direct => (direct, value),
+            AddressMode::IndirectA => {
+                let pointer = self.memory[direct].a_value;
+                (wrap_add(direct, pointer, core_size), pointer)
+            }
+            AddressMode::IndirectB => {
+                let pointer = self.memory[direct].b_value;
+                (wrap_add(direct, pointer, core_size), pointer)
+            }
+            AddressMode::PreDecIndirectA => {
+                self.memory[direct].a_value = self.memory[direct]
+                    .a_value
+                    .wrapping_sub(1)
+                    .rem_euclid(core_size as Offset);
+                let pointer = self.memory[direct].a_value;
+                (wrap_add(direct, pointer, core_size), pointer)
+            }
+            AddressMode::PreDecIndirectB => {
+                self.memory[direct].b_value = self.memory[direct]
+                    .b_value
+                    .wrapping_sub(1)
+                    .rem_euclid(core_size as Offset);
+                let pointer = self.memory[direct].b_value;
+                (wrap_add(direct, pointer, core_size), pointer)
+            }
+            AddressMode::PostIncIndirectA => {
+                let pointer = self.memory[direct].a_value;
+                self.memory[direct].a_value = pointer.wrapping_add(1).rem_euclid(core_size as Offset);
+                (wrap_add(direct, pointer, core_size), pointer)
+            }
+            AddressMode::PostIncIndirectB => {
+                let pointer = self.memory[direct].b_value;
+                self.memory[direct].b_value = pointer.wrapping_add(1).rem_euclid(core_size as Offset);
+                (wrap_add(direct, pointer, core_size), pointer)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Core {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (offset, cell) in self.memory.iter().enumerate() {
+            if *cell != Cell::default() {
+                writeln!(f, "{offset:>5}  {cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn wrap_add(base: usize, delta: Offset, core_size: usize) -> usize {
+    (base as i64 + delta as i64).rem_euclid(core_size as i64) as usize
+}
+
+/// Canonicalize an arithmetic result to `[0, core_size)`, the same way
+/// every address computation already is, so that values written by
+/// `ADD`/`SUB`/`MUL`/`DIV`/`MOD` stay comparable with `CMP`/`SEQ`/`SNE`/`SLT`.
+fn wrap_value(value: Offset, core_size: usize) -> Offset {
+    value.rem_euclid(core_size as Offset)
+}
+
+fn wrap_distance(a: usize, b: usize, core_size: usize) -> usize {
+    let diff = (a as i64 - b as i64).unsigned_abs() as usize;
+    diff.min(core_size - diff)
+}
+
+/// Which field of a `Cell` a modifier reads from or writes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    A,
+    B,
+}
+
+fn field(cell: Cell, which: Field) -> Offset {
+    match which {
+        Field::A => cell.a_value,
+        Field::B => cell.b_value,
+    }
+}
+
+fn set_field(cell: &mut Cell, which: Field, value: Offset) {
+    match which {
+        Field::A => cell.a_value = value,
+        Field::B => cell.b_value = value,
+    }
+}
+
+/// The `(source, destination)` field pairs a modifier operates on, per
+/// ICWS'94 section A.2.1.4. `Modifier::I` is handled specially by callers
+/// for `MOV`/`CMP`/`SEQ`/`SNE` (it means "whole instruction"), but for
+/// `SLT` and the arithmetic opcodes it behaves exactly like `F`.
+fn modifier_fields(modifier: Modifier) -> &'static [(Field, Field)] {
+    use Field::{A, B};
+
+    match modifier {
+        Modifier::A => &[(A, A)],
+        Modifier::B => &[(B, B)],
+        Modifier::AB => &[(A, B)],
+        Modifier::BA => &[(B, A)],
+        Modifier::F | Modifier::I => &[(A, A), (B, B)],
+        Modifier::X => &[(A, B), (B, A)],
+    }
+}
+
+/// Whether `a` and `b` are equal under `modifier`'s field selection, for
+/// `CMP`/`SEQ`/`SNE` under any modifier other than `I` (which compares the
+/// whole instruction instead).
+fn fields_equal(a: Cell, b: Cell, modifier: Modifier) -> bool {
+    modifier_fields(modifier)
+        .iter()
+        .all(|&(src, dst)| field(a, src) == field(b, dst))
+}
+
+#[cfg(test)]
+mod test {
+    use corewars_core::Value;
+    use corewars_parser::Instruction;
+
+    use super::*;
+
+    fn instr(
+        opcode: Opcode,
+        a_mode: AddressMode,
+        a_value: Offset,
+        b_mode: AddressMode,
+        b_value: Offset,
+    ) -> Instruction {
+        Instruction {
+            opcode,
+            modifier: Some(Modifier::I),
+            a_mode,
+            a_value: Value::Literal(a_value),
+            b_mode,
+            b_value: Value::Literal(b_value),
+        }
+    }
+
+    fn single_instruction_warrior(instruction: Instruction) -> corewars_parser::Warrior {
+        corewars_parser::Warrior {
+            instructions: vec![instruction],
+            ..Default::default()
+        }
+    }
+
+    fn instr_modifier(
+        opcode: Opcode,
+        modifier: Modifier,
+        a_mode: AddressMode,
+        a_value: Offset,
+        b_mode: AddressMode,
+        b_value: Offset,
+    ) -> Instruction {
+        Instruction {
+            opcode,
+            modifier: Some(modifier),
+            a_mode,
+            a_value: Value::Literal(a_value),
+            b_mode,
+            b_value: Value::Literal(b_value),
+        }
+    }
+
+    #[test]
+    fn mov_a_only_copies_the_a_field_unlike_mov_i() {
+        let warrior = corewars_parser::Warrior {
+            instructions: vec![
+                instr_modifier(
+                    Opcode::Mov,
+                    Modifier::A,
+                    AddressMode::Direct,
+                    1,
+                    AddressMode::Direct,
+                    2,
+                ),
+                instr_modifier(
+                    Opcode::Dat,
+                    Modifier::F,
+                    AddressMode::Direct,
+                    11,
+                    AddressMode::Direct,
+                    22,
+                ),
+                instr_modifier(
+                    Opcode::Dat,
+                    Modifier::F,
+                    AddressMode::Direct,
+                    33,
+                    AddressMode::Direct,
+                    44,
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let mut core = Core::default();
+        core.load_warrior(&warrior).unwrap();
+
+        let core_size = core.memory.len();
+        let pc = *core.warriors[0].process_queue.front().unwrap();
+        core.step(0, pc, core_size);
+
+        let dest_addr = wrap_add(pc, 2, core_size);
+        assert_eq!(core.memory[dest_addr].a_value, 11, "A-field should be copied");
+        assert_eq!(
+            core.memory[dest_addr].b_value, 44,
+            "MOV.A must leave the B-field untouched, unlike MOV.I"
+        );
+    }
+
+    #[test]
+    fn add_result_wraps_modulo_core_size() {
+        let warrior = corewars_parser::Warrior {
+            instructions: vec![
+                instr_modifier(
+                    Opcode::Add,
+                    Modifier::AB,
+                    AddressMode::Immediate,
+                    7,
+                    AddressMode::Direct,
+                    1,
+                ),
+                instr(Opcode::Dat, AddressMode::Direct, 0, AddressMode::Direct, 5),
+            ],
+            ..Default::default()
+        };
+
+        let mut core = Core::with_core_size(10);
+        core.load_warrior(&warrior).unwrap();
+
+        let core_size = core.memory.len();
+        let pc = *core.warriors[0].process_queue.front().unwrap();
+        core.step(0, pc, core_size);
+
+        let dest_addr = wrap_add(pc, 1, core_size);
+        assert_eq!(
+            core.memory[dest_addr].b_value, 2,
+            "5 + 7 should wrap to 2 mod a core size of 10, not store 12"
+        );
+    }
+
+    #[test]
+    fn battle_declares_survivor_when_one_warrior_dies_immediately() {
+        let mut core = Core::default();
+
+        let dies_immediately = single_instruction_warrior(instr(
+            Opcode::Dat,
+            AddressMode::Direct,
+            0,
+            AddressMode::Direct,
+            0,
+        ));
+        let loops_forever = single_instruction_warrior(instr(
+            Opcode::Jmp,
+            AddressMode::Direct,
+            0,
+            AddressMode::Direct,
+            0,
+        ));
+
+        core.load_warrior(&dies_immediately).unwrap();
+        core.load_warrior(&loops_forever).unwrap();
+
+        let outcomes = core.run_battle(None);
+
+        assert_eq!(outcomes[0].died_at_cycle, Some(0));
+        assert_eq!(outcomes[1].died_at_cycle, None);
+    }
+
+    #[test]
+    fn single_warrior_battle_actually_runs_to_completion() {
+        let mut core = Core::default();
+
+        let warrior = single_instruction_warrior(instr(
+            Opcode::Dat,
+            AddressMode::Direct,
+            0,
+            AddressMode::Direct,
+            0,
+        ));
+
+        core.load_warrior(&warrior).unwrap();
+
+        let outcomes = core.run_battle(None);
+
+        assert_eq!(
+            core.steps_taken(),
+            1,
+            "the lone warrior's single DAT should actually have been stepped"
+        );
+        assert_eq!(outcomes[0].died_at_cycle, Some(0));
+    }
+
+    #[test]
+    fn p_space_cell_zero_is_read_only() {
+        let mut p_space = PSpace::new(4);
+
+        p_space.seed(123);
+        p_space.store(0, 456);
+
+        assert_eq!(p_space.load(0), 123);
+    }
+
+    #[test]
+    fn p_space_indices_wrap() {
+        let mut p_space = PSpace::new(4);
+
+        p_space.store(5, 42);
+
+        assert_eq!(p_space.load(1), 42);
+    }
+
+    #[test]
+    fn ldp_stp_round_trip_through_p_space() {
+        let mut core = Core::default();
+
+        // index 0: plain data cell holding the value to round-trip
+        // index 1: STP $-1, $7 -- stores memory[index 0].b_value into p_space[7]
+        // index 2: LDP $7, $1  -- loads p_space[7] back into memory[index 3].b_value
+        let warrior = corewars_parser::Warrior {
+            instructions: vec![
+                instr(Opcode::Dat, AddressMode::Direct, 0, AddressMode::Direct, 99),
+                instr(Opcode::Stp, AddressMode::Direct, -1, AddressMode::Direct, 7),
+                instr(Opcode::Ldp, AddressMode::Direct, 7, AddressMode::Direct, 1),
+            ],
+            start_offset: 1,
+            ..Default::default()
+        };
+
+        core.load_warrior(&warrior).unwrap();
+
+        let core_size = core.memory.len();
+        let pc_stp = core.warriors[0].process_queue.pop_front().unwrap();
+        core.step(0, pc_stp, core_size);
+
+        let pc_ldp = core.warriors[0].process_queue.pop_front().unwrap();
+        core.step(0, pc_ldp, core_size);
+
+        let write_addr = wrap_add(pc_ldp, 1, core_size);
+        assert_eq!(core.memory[write_addr].b_value, 99);
+    }
+}
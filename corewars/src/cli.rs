@@ -28,6 +28,12 @@ struct CliOptions {
     #[clap(long, short)]
     verbose: bool,
 
+    /// Which ICWS standard to assemble and simulate against. '88 mode
+    /// rejects opcodes, address modes, and explicit modifiers introduced in
+    /// '94, and always infers modifiers via the '88->'94 conversion.
+    #[clap(long, default_value = "icws-94")]
+    standard: corewars_core::Standard,
+
     /// Input file; use "-" to read from stdin
     input_file: PathBuf,
 }
@@ -47,67 +53,100 @@ enum Command {
         no_expand: bool,
     },
 
-    /// Run a warrior to completion
+    /// Run a battle between one or more warriors to completion
     #[clap(name = "run")]
     Run {
+        /// Additional warrior files to load into the same core, for battles
+        /// between more than one warrior. Pass `--warrior` once per
+        /// additional combatant.
+        #[clap(long, short = 'w')]
+        warrior: Vec<PathBuf>,
+
         /// The max number of cycles to run. Defaults to
         #[clap(long, short)]
         max_cycles: Option<usize>,
+
+        /// The number of cells in each warrior's private P-space. Defaults
+        /// to `CORESIZE / 16 + 1`, per the ICWS'94 P-space extension.
+        #[clap(long)]
+        p_space_size: Option<usize>,
+
+        /// The value to seed cell 0 of each warrior's P-space with, i.e. the
+        /// result carried over from a previous round. P-space is only
+        /// meaningful across multiple rounds, so this defaults to 0.
+        #[clap(long, default_value = "0")]
+        p_space_seed: corewars_core::Offset,
     },
 }
 
 pub fn run() -> Result<(), Box<dyn Error>> {
     let cli_options = CliOptions::parse();
-
-    let mut input = String::new();
-
-    if cli_options.input_file == *IO_SENTINEL {
-        io::stdin().read_to_string(&mut input)?;
-    } else {
-        input = fs::read_to_string(cli_options.input_file)?;
-    }
-
-    let parsed_core = match parser::parse(input.as_str()) {
-        parser::Result::Ok(warrior, warnings) => {
-            print_warnings(&warnings);
-            Ok(warrior)
-        }
-        parser::Result::Err(err, warnings) => {
-            print_warnings(&warnings);
-            Err(err)
-        }
-    }?;
+    let standard = cli_options.standard;
 
     match cli_options.command {
         Command::Dump {
             output_file,
             no_expand,
         } => {
-            if no_expand {
-                unimplemented!()
-            }
+            let parsed_core = read_and_parse(&cli_options.input_file, standard)?;
+
+            let output = if no_expand {
+                parsed_core.to_string()
+            } else {
+                parsed_core.to_expanded_load_file()?
+            };
 
             if output_file == *IO_SENTINEL {
-                println!("{parsed_core}");
+                println!("{output}");
             } else {
-                fs::write(output_file, format!("{parsed_core}\n"))?;
+                fs::write(output_file, format!("{output}\n"))?;
             };
         }
-        Command::Run { max_cycles } => {
-            let mut core = Core::default();
-            core.load_warrior(&parsed_core)?;
-
-            match core.run(max_cycles) {
-                Ok(_) => println!(
-                    "Warrior stopped after {}max of {} cycles",
-                    if max_cycles.is_some() {
-                        "specified "
-                    } else {
-                        ""
-                    },
-                    core.steps_taken()
-                ),
-                Err(err) => println!("Warrior failed after {} steps: {err}", core.steps_taken()),
+        Command::Run {
+            warrior,
+            max_cycles,
+            p_space_size,
+            p_space_seed,
+        } => {
+            let warrior_paths = std::iter::once(cli_options.input_file).chain(warrior);
+
+            let warriors = warrior_paths
+                .map(|path| {
+                    let name = warrior_name(&path);
+                    read_and_parse(&path, standard).map(|parsed| (name, parsed))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut core = match p_space_size {
+                Some(size) => Core::with_p_space_size(size),
+                None => Core::default(),
+            };
+
+            for (name, parsed) in &warriors {
+                let p_space = core.load_warrior(parsed)?;
+                p_space.seed(p_space_seed);
+                if cli_options.verbose {
+                    println!("Loaded {name}");
+                }
+            }
+
+            let outcomes = core.run_battle(max_cycles);
+            let mut survivors = Vec::new();
+
+            for ((name, _), outcome) in warriors.iter().zip(&outcomes) {
+                match outcome.died_at_cycle {
+                    Some(cycle) => println!("{name} died on cycle {cycle}"),
+                    None => {
+                        println!("{name} survived to the end of the battle");
+                        survivors.push(name);
+                    }
+                }
+            }
+
+            match survivors.as_slice() {
+                [] => println!("Draw: no warriors survived"),
+                [winner] => println!("Winner: {winner}"),
+                survivors => println!("Tie between {} warriors", survivors.len()),
             }
 
             if cli_options.verbose {
@@ -119,6 +158,44 @@ pub fn run() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+fn read_and_parse(
+    input_file: &PathBuf,
+    standard: corewars_core::Standard,
+) -> Result<parser::Warrior, Box<dyn Error>> {
+    let mut input = String::new();
+
+    if input_file == &*IO_SENTINEL {
+        io::stdin().read_to_string(&mut input)?;
+    } else {
+        input = fs::read_to_string(input_file)?;
+    }
+
+    let parsed = match parser::parse(input.as_str(), standard) {
+        parser::Result::Ok(warrior, warnings) => {
+            print_warnings(&warnings);
+            Ok(warrior)
+        }
+        parser::Result::Err(err, warnings) => {
+            print_warnings(&warnings);
+            Err(err)
+        }
+    }?;
+
+    Ok(parsed)
+}
+
+/// Derive a human-readable label for a warrior from its input file name, for
+/// use in battle reporting. Falls back to "stdin" for the `-` sentinel.
+fn warrior_name(path: &PathBuf) -> String {
+    if path == &*IO_SENTINEL {
+        return "stdin".to_owned();
+    }
+
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string_lossy().into_owned())
+}
+
 fn print_warnings(warnings: &[parser::Warning]) {
     for warning in warnings.iter() {
         eprintln!("Warning: {warning}");